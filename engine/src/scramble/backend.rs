@@ -0,0 +1,329 @@
+use super::fourier::{Float, FloatComplex};
+
+/// Executes the 2D forward/inverse transforms `FourierScrambler` needs.
+pub trait FftBackend: Send + Sync {
+    /// Forward 2D FFT of an `n x n` row-major buffer, in place.
+    fn forward(&self, data: &mut [FloatComplex], n: usize);
+    /// Inverse 2D FFT of an `n x n` row-major buffer, in place, scaled by `1/n²`.
+    fn inverse(&self, data: &mut [FloatComplex], n: usize);
+}
+
+/// CPU backend built on `rustfft`, parallelized across rows/columns with rayon.
+pub struct RustfftBackend {
+    fft: std::sync::Arc<dyn rustfft::Fft<Float>>,
+    ifft: std::sync::Arc<dyn rustfft::Fft<Float>>,
+}
+
+impl RustfftBackend {
+    pub fn new(padded_size: usize) -> Self {
+        let mut planner = rustfft::FftPlanner::new();
+        Self {
+            fft: planner.plan_fft_forward(padded_size),
+            ifft: planner.plan_fft_inverse(padded_size),
+        }
+    }
+}
+
+impl FftBackend for RustfftBackend {
+    fn forward(&self, data: &mut [FloatComplex], n: usize) {
+        use rayon::prelude::*;
+        data.par_chunks_mut(n).for_each(|row| self.fft.process(row));
+        let mut columns = super::fourier::transpose(data, n);
+        columns.par_chunks_mut(n).for_each(|col| self.fft.process(col));
+        super::fourier::untranspose(&columns, data, n);
+    }
+
+    fn inverse(&self, data: &mut [FloatComplex], n: usize) {
+        use rayon::prelude::*;
+        data.par_chunks_mut(n).for_each(|row| self.ifft.process(row));
+        let mut columns = super::fourier::transpose(data, n);
+        columns.par_chunks_mut(n).for_each(|col| self.ifft.process(col));
+        super::fourier::untranspose(&columns, data, n);
+        let scale = 1.0 / (n * n) as Float;
+        data.par_iter_mut().for_each(|val| *val = *val * scale);
+    }
+}
+
+#[cfg(feature = "gpu")]
+mod gpu {
+    use super::{Float, FftBackend, FloatComplex};
+    use wgpu::util::DeviceExt;
+
+    const BUTTERFLY_SHADER: &str = r#"
+struct Params {
+    n: u32,
+    stage: u32,
+    direction: f32, // +1.0 forward, -1.0 inverse
+    _pad: f32,
+};
+
+@group(0) @binding(0) var<storage, read_write> data: array<vec2<f32>>;
+@group(0) @binding(1) var<uniform> params: Params;
+
+fn bit_reverse(v: u32, bits: u32) -> u32 {
+    var x = v;
+    var r: u32 = 0u;
+    for (var i: u32 = 0u; i < bits; i = i + 1u) {
+        r = (r << 1u) | (x & 1u);
+        x = x >> 1u;
+    }
+    return r;
+}
+
+@compute @workgroup_size(64)
+fn bit_reverse_permute(@builtin(global_invocation_id) gid: vec3<u32>) {
+    let row = gid.y;
+    let col = gid.x;
+    if (col >= params.n) { return; }
+    var bits: u32 = 0u;
+    var m: u32 = params.n;
+    while (m > 1u) { m = m >> 1u; bits = bits + 1u; }
+    let swap_idx = bit_reverse(col, bits);
+    if (swap_idx > col) {
+        let base = row * params.n;
+        let a = data[base + col];
+        let b = data[base + swap_idx];
+        data[base + col] = b;
+        data[base + swap_idx] = a;
+    }
+}
+
+// One radix-2 Cooley-Tukey decimation-in-time butterfly stage, dispatched
+// once per log2(n) stage after the bit-reversal reorder above.
+@compute @workgroup_size(64)
+fn butterfly_stage(@builtin(global_invocation_id) gid: vec3<u32>) {
+    let row = gid.y;
+    let pair = gid.x;
+    let half: u32 = 1u << params.stage;
+    let span: u32 = half << 1u;
+    let group = pair / half;
+    let offset = pair % half;
+    if (group * span >= params.n) { return; }
+
+    let base = row * params.n + group * span;
+    let i = base + offset;
+    let j = i + half;
+
+    let angle = params.direction * -2.0 * 3.14159265358979 * f32(offset) / f32(span);
+    let tw = vec2<f32>(cos(angle), sin(angle));
+
+    let a = data[i];
+    let b = data[j];
+    let t = vec2<f32>(b.x * tw.x - b.y * tw.y, b.x * tw.y + b.y * tw.x);
+    data[i] = a + t;
+    data[j] = a - t;
+}
+"#;
+
+    /// GPU backend: uploads each row (then, via transpose, each column) as a
+    /// batch of independent power-of-two FFTs, runs one `butterfly_stage`
+    /// dispatch per `log2(n)` stage preceded by a bit-reversal reorder pass,
+    /// and reads the transformed buffer back.
+    pub struct WgpuFftBackend {
+        device: wgpu::Device,
+        queue: wgpu::Queue,
+        bit_reverse_pipeline: wgpu::ComputePipeline,
+        butterfly_pipeline: wgpu::ComputePipeline,
+        bind_group_layout: wgpu::BindGroupLayout,
+    }
+
+    impl WgpuFftBackend {
+        /// Returns `None` if no suitable adapter is available or `n` isn't a
+        /// power of two (the butterfly shader assumes radix-2 stages); the
+        /// caller falls back to [`super::RustfftBackend`] in that case.
+        pub fn try_new(n: usize) -> Option<Self> {
+            if !n.is_power_of_two() {
+                return None;
+            }
+            let instance = wgpu::Instance::default();
+            let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::HighPerformance,
+                ..Default::default()
+            }))?;
+            let (device, queue) = pollster::block_on(adapter.request_device(
+                &wgpu::DeviceDescriptor {
+                    label: Some("scrambler-fft-device"),
+                    ..Default::default()
+                },
+                None,
+            ))
+            .ok()?;
+
+            let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some("scrambler-fft-butterfly"),
+                source: wgpu::ShaderSource::Wgsl(BUTTERFLY_SHADER.into()),
+            });
+            let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("scrambler-fft-bind-group-layout"),
+                entries: &[
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStages::COMPUTE,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Storage { read_only: false },
+                            has_dynamic_offset: false,
+                            min_binding_size: None,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: wgpu::ShaderStages::COMPUTE,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Uniform,
+                            has_dynamic_offset: false,
+                            min_binding_size: None,
+                        },
+                        count: None,
+                    },
+                ],
+            });
+            let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("scrambler-fft-pipeline-layout"),
+                bind_group_layouts: &[&bind_group_layout],
+                push_constant_ranges: &[],
+            });
+            let bit_reverse_pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: Some("scrambler-fft-bit-reverse"),
+                layout: Some(&pipeline_layout),
+                module: &shader,
+                entry_point: "bit_reverse_permute",
+                compilation_options: Default::default(),
+                cache: None,
+            });
+            let butterfly_pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: Some("scrambler-fft-butterfly"),
+                layout: Some(&pipeline_layout),
+                module: &shader,
+                entry_point: "butterfly_stage",
+                compilation_options: Default::default(),
+                cache: None,
+            });
+
+            Some(Self {
+                device,
+                queue,
+                bit_reverse_pipeline,
+                butterfly_pipeline,
+                bind_group_layout,
+            })
+        }
+
+        /// Runs the bit-reversal reorder and all `log2(n)` butterfly stages
+        /// over `rows` independent length-`n` rows stored contiguously in
+        /// `data`, then reads the result back into `data`.
+        fn transform_rows(&self, data: &mut [FloatComplex], n: usize, rows: usize, direction: f32) {
+            let floats: Vec<[f32; 2]> = data
+                .iter()
+                .map(|c| [c.re as f32, c.im as f32])
+                .collect();
+            let buffer = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("scrambler-fft-data"),
+                contents: bytemuck::cast_slice(&floats),
+                usage: wgpu::BufferUsages::STORAGE
+                    | wgpu::BufferUsages::COPY_SRC
+                    | wgpu::BufferUsages::COPY_DST,
+            });
+            let readback = self.device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("scrambler-fft-readback"),
+                size: (floats.len() * std::mem::size_of::<[f32; 2]>()) as u64,
+                usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            });
+
+            let stages = n.trailing_zeros();
+            let mut encoder = self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("scrambler-fft-encoder"),
+            });
+            let workgroups_x = (n as u32).div_ceil(64);
+
+            let dispatch = |encoder: &mut wgpu::CommandEncoder, pipeline: &wgpu::ComputePipeline, stage: u32| {
+                let params = [n as u32, stage, direction.to_bits(), 0.0f32.to_bits()];
+                let uniform = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                    label: Some("scrambler-fft-params"),
+                    contents: bytemuck::cast_slice(&params),
+                    usage: wgpu::BufferUsages::UNIFORM,
+                });
+                let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+                    label: Some("scrambler-fft-bind-group"),
+                    layout: &self.bind_group_layout,
+                    entries: &[
+                        wgpu::BindGroupEntry { binding: 0, resource: buffer.as_entire_binding() },
+                        wgpu::BindGroupEntry { binding: 1, resource: uniform.as_entire_binding() },
+                    ],
+                });
+                let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                    label: Some("scrambler-fft-pass"),
+                    timestamp_writes: None,
+                });
+                pass.set_pipeline(pipeline);
+                pass.set_bind_group(0, &bind_group, &[]);
+                pass.dispatch_workgroups(workgroups_x, rows as u32, 1);
+            };
+
+            dispatch(&mut encoder, &self.bit_reverse_pipeline, 0);
+            for stage in 0..stages {
+                dispatch(&mut encoder, &self.butterfly_pipeline, stage);
+            }
+            encoder.copy_buffer_to_buffer(&buffer, 0, &readback, 0, readback.size());
+            self.queue.submit(Some(encoder.finish()));
+
+            let slice = readback.slice(..);
+            let (tx, rx) = std::sync::mpsc::channel();
+            slice.map_async(wgpu::MapMode::Read, move |res| {
+                let _ = tx.send(res);
+            });
+            self.device.poll(wgpu::Maintain::Wait);
+            rx.recv().unwrap().unwrap();
+            let bytes = slice.get_mapped_range();
+            let result: &[[f32; 2]] = bytemuck::cast_slice(&bytes);
+            for (dst, src) in data.iter_mut().zip(result) {
+                *dst = FloatComplex::new(src[0] as Float, src[1] as Float);
+            }
+        }
+    }
+
+    impl FftBackend for WgpuFftBackend {
+        fn forward(&self, data: &mut [FloatComplex], n: usize) {
+            self.transform_rows(data, n, n, 1.0);
+            let mut columns = super::super::fourier::transpose(data, n);
+            self.transform_rows(&mut columns, n, n, 1.0);
+            super::super::fourier::untranspose(&columns, data, n);
+        }
+
+        fn inverse(&self, data: &mut [FloatComplex], n: usize) {
+            self.transform_rows(data, n, n, -1.0);
+            let mut columns = super::super::fourier::transpose(data, n);
+            self.transform_rows(&mut columns, n, n, -1.0);
+            super::super::fourier::untranspose(&columns, data, n);
+            let scale = 1.0 / (n * n) as Float;
+            for val in data.iter_mut() {
+                *val = *val * scale;
+            }
+        }
+    }
+}
+
+#[cfg(feature = "gpu")]
+pub use gpu::WgpuFftBackend;
+
+/// Picks a backend for an image whose larger dimension is `max_dim`, and
+/// returns it along with the padded size the caller must use for every
+/// padding/unpadding step so it stays in sync with the backend's plan.
+///
+/// The two backends disagree on what "optimal" padding means: `RustfftBackend`
+/// wants the 5-smooth size `get_optimal_fft_size` computes, while the
+/// radix-2 `WgpuFftBackend` needs a power of two. Picking the GPU backend
+/// first, at its own size, means the CPU path's 5-smooth sizing (which is
+/// usually *not* a power of two) never silently forces a GPU fallback.
+pub fn select_backend(max_dim: usize) -> (std::sync::Arc<dyn FftBackend>, usize) {
+    #[cfg(feature = "gpu")]
+    {
+        let gpu_size = max_dim.max(1).next_power_of_two();
+        if let Some(gpu) = WgpuFftBackend::try_new(gpu_size) {
+            return (std::sync::Arc::new(gpu), gpu_size);
+        }
+    }
+    let cpu_size = super::fourier::get_optimal_fft_size(max_dim);
+    (std::sync::Arc::new(RustfftBackend::new(cpu_size)), cpu_size)
+}
@@ -1,31 +1,41 @@
 use image::{DynamicImage, GenericImageView, ImageBuffer, Rgb};
 use ndarray::Array2;
-use num_complex::Complex64;
-use rustfft::{Fft, FftPlanner};
+use rustfft::num_complex::Complex;
 use rand::{Rng, SeedableRng};
 use rand::rngs::StdRng;
+use rayon::prelude::*;
 use crate::Result;
-use super::types::{FourierOptions, FrequencyRange, PaddingMode};
+use super::types::{FourierOptions, FrequencyRange, PaddingMode, SpectrumView};
+use super::backend::{select_backend, FftBackend};
 use face_detection::{detect_face_regions, load_face_detector};
 use crate::FaceDetectionOptions;
 use crate::BackgroundMode;
 use image::GenericImage;
+
+/// Floating-point precision used throughout the Fourier pipeline.
+#[cfg(not(feature = "f32"))]
+pub type Float = f64;
+#[cfg(feature = "f32")]
+pub type Float = f32;
+
+pub(crate) type FloatComplex = Complex<Float>;
+
 pub struct FourierScrambler {
     width: usize,
     height: usize,
-    fft: std::sync::Arc<dyn Fft<f64>>,
-    ifft: std::sync::Arc<dyn Fft<f64>>,
+    backend: std::sync::Arc<dyn FftBackend>,
+    // The size the selected backend actually plans its transform for; the
+    // backend and the CPU-only padding/unpadding code must agree on this,
+    // so it's resolved once here rather than recomputed per channel.
+    padded_size: usize,
     options: FourierOptions,
     rng: StdRng,
+    seed: Option<u64>,
 }
 
 impl FourierScrambler {
     pub fn new(width: usize, height: usize, options: FourierOptions, seed: Option<u64>) -> Self {
-        // Determine the padded size (square) based on the maximum dimension.
-        let padded_size = get_optimal_fft_size(width.max(height));
-        let mut planner = FftPlanner::new();
-        let fft = planner.plan_fft_forward(padded_size);
-        let ifft = planner.plan_fft_inverse(padded_size);
+        let (backend, padded_size) = select_backend(width.max(height));
         let rng = if let Some(seed) = seed {
             StdRng::seed_from_u64(seed)
         } else {
@@ -34,25 +44,120 @@ impl FourierScrambler {
         Self {
             width,
             height,
-            fft,
-            ifft,
+            backend,
+            padded_size,
             options,
             rng,
+            seed,
         }
     }
 
     /// Scrambles a single image.
+    ///
+    /// The three channels are processed concurrently. Each gets its own seeded
+    /// sub-stream of the scrambler's RNG (drawn up front, in channel order) so
+    /// the result stays deterministic for a given seed regardless of how the
+    /// channels are scheduled across threads.
     pub fn scramble(&mut self, image: &DynamicImage) -> Result<DynamicImage> {
         let (width, height) = image.dimensions();
         self.width = width as usize;
         self.height = height as usize;
         let channels = self.split_channels(image)?;
-        let processed_channels: Vec<Array2<f64>> = channels
+        let channel_seeds: Vec<u64> = (0..channels.len())
+            .map(|_| self.rng.gen_range(u64::MIN..=u64::MAX))
+            .collect();
+        let processed_channels: Vec<Array2<Float>> = channels
             .into_iter()
-            .map(|channel| self.process_channel(channel))
+            .zip(channel_seeds)
+            .collect::<Vec<_>>()
+            .into_par_iter()
+            .map(|(channel, seed)| self.process_channel(channel, seed))
             .collect::<Result<Vec<_>>>()?;
         self.combine_channels(processed_channels)
     }
+
+    /// Reverses a [`Self::scramble`] call, recovering the original image from
+    /// its phase-scrambled counterpart.
+    ///
+    /// Requires a fresh `FourierScrambler` built with the same `seed` and
+    /// `options` used to scramble, and rejects `PaddingMode::Reflect` and
+    /// `PaddingMode::Wrap` (see the guards below). Even with
+    /// `PaddingMode::Zero` the result is approximate: the scrambled image was
+    /// clamped to `[0, 1]` and quantized to 8 bits, and whatever the scramble
+    /// spread into the padded border was cropped away.
+    pub fn unscramble(&mut self, image: &DynamicImage) -> Result<DynamicImage> {
+        if self.seed.is_none() {
+            return Err(
+                "unscrambling requires the scrambler to have been built with a seed".to_string().into(),
+            );
+        }
+        if self.options.intensity == 1.0 {
+            return Err(
+                "intensity of 1.0 discards the original phase entirely and cannot be reversed"
+                    .to_string()
+                    .into(),
+            );
+        }
+        if self.options.padding_mode != PaddingMode::Zero {
+            return Err(
+                "unscrambling is only exact for PaddingMode::Zero; Reflect and Wrap pad from \
+                 content that differs between the forward and reverse passes"
+                    .to_string()
+                    .into(),
+            );
+        }
+        if !self.options.phase_scramble {
+            return Err(
+                "unscrambling requires options.phase_scramble to have been true on the scrambler \
+                 that produced this image; there is no scrambled phase to recover"
+                    .to_string()
+                    .into(),
+            );
+        }
+        let (width, height) = image.dimensions();
+        self.width = width as usize;
+        self.height = height as usize;
+        let channels = self.split_channels(image)?;
+        let channel_seeds: Vec<u64> = (0..channels.len())
+            .map(|_| self.rng.gen_range(u64::MIN..=u64::MAX))
+            .collect();
+        let processed_channels: Vec<Array2<Float>> = channels
+            .into_iter()
+            .zip(channel_seeds)
+            .collect::<Vec<_>>()
+            .into_par_iter()
+            .map(|(channel, seed)| self.unscramble_channel(channel, seed))
+            .collect::<Result<Vec<_>>>()?;
+        self.combine_channels(processed_channels)
+    }
+
+    /// Renders a diagnostic picture of an image's frequency content instead
+    /// of inverse-transforming it, using the same padding/FFT plumbing as
+    /// [`Self::scramble`]. The three channels are averaged into a single
+    /// luminance field before transforming.
+    pub fn visualize_spectrum(&mut self, image: &DynamicImage, view: SpectrumView) -> Result<DynamicImage> {
+        let (width, height) = image.dimensions();
+        self.width = width as usize;
+        self.height = height as usize;
+        let channels = self.split_channels(image)?;
+        let mut luma = Array2::zeros((self.height, self.width));
+        for y in 0..self.height {
+            for x in 0..self.width {
+                luma[[y, x]] =
+                    (channels[0][[y, x]] + channels[1][[y, x]] + channels[2][[y, x]]) / 3.0;
+            }
+        }
+        let padded = self.apply_padding(&luma)?;
+        let n = padded.dim().0;
+        let mut complex_data = self.to_complex(&padded);
+        self.fft2d(&mut complex_data, n);
+        let image = match view {
+            SpectrumView::Magnitude => render_magnitude(&complex_data, n),
+            SpectrumView::Phase => render_phase_domain_color(&complex_data, n),
+        };
+        Ok(DynamicImage::ImageRgb8(image))
+    }
+
     pub fn scramble_with_face_detection(
         &mut self,
         image: &DynamicImage,
@@ -115,13 +220,18 @@ impl FourierScrambler {
     }
     /// Processes a single channel: pads the image, computes its 2D FFT,
     /// replaces its phase while preserving the magnitude, and then computes the inverse FFT.
-    fn process_channel(&mut self, channel: Array2<f64>) -> Result<Array2<f64>> {
+    ///
+    /// Takes `&self` rather than `&mut self` so channels can be processed in
+    /// parallel; the phase scramble draws from a `StdRng` seeded just for this
+    /// channel instead of the scrambler's shared RNG.
+    fn process_channel(&self, channel: Array2<Float>, seed: u64) -> Result<Array2<Float>> {
         let padded = self.apply_padding(&channel)?;
         let n = padded.dim().0; // padded is square of size n x n
         let mut complex_data = self.to_complex(&padded);
         self.fft2d(&mut complex_data, n);
         if self.options.phase_scramble {
-            self.phase_scramble(&mut complex_data);
+            let mut rng = StdRng::seed_from_u64(seed);
+            self.phase_scramble(&mut complex_data, &mut rng);
         }
         self.ifft2d(&mut complex_data, n);
         let mut result = self.remove_padding(&complex_data, channel.dim())?;
@@ -132,59 +242,46 @@ impl FourierScrambler {
         Ok(result)
     }
 
-    /// Computes the 2D FFT by applying the 1D FFT along rows then columns.
-    fn fft2d(&self, data: &mut [Complex64], n: usize) {
-        // FFT each row.
-        for row in 0..n {
-            let start = row * n;
-            let end = start + n;
-            self.fft.process(&mut data[start..end]);
-        }
-        // FFT each column.
-        let mut column = vec![Complex64::new(0.0, 0.0); n];
-        for col in 0..n {
-            for row in 0..n {
-                column[row] = data[row * n + col];
-            }
-            self.fft.process(&mut column);
-            for row in 0..n {
-                data[row * n + col] = column[row];
-            }
+    /// Inverse of [`Self::process_channel`]: computes the 2D FFT, solves each
+    /// coefficient's phase back to its pre-scramble value, then inverse
+    /// transforms.
+    fn unscramble_channel(&self, channel: Array2<Float>, seed: u64) -> Result<Array2<Float>> {
+        let padded = self.apply_padding(&channel)?;
+        let n = padded.dim().0;
+        let mut complex_data = self.to_complex(&padded);
+        self.fft2d(&mut complex_data, n);
+        let mut rng = StdRng::seed_from_u64(seed);
+        self.phase_unscramble(&mut complex_data, &mut rng);
+        self.ifft2d(&mut complex_data, n);
+        let mut result = self.remove_padding(&complex_data, channel.dim())?;
+        for val in result.iter_mut() {
+            *val = val.max(0.0).min(1.0);
         }
+        Ok(result)
     }
 
-    /// Computes the 2D inverse FFT by applying the 1D IFFT along rows then columns.
-    /// The result is scaled by 1/(n*n).
-    fn ifft2d(&self, data: &mut [Complex64], n: usize) {
-        // IFFT each row.
-        for row in 0..n {
-            let start = row * n;
-            let end = start + n;
-            self.ifft.process(&mut data[start..end]);
-        }
-        // IFFT each column.
-        let mut column = vec![Complex64::new(0.0, 0.0); n];
-        for col in 0..n {
-            for row in 0..n {
-                column[row] = data[row * n + col];
-            }
-            self.ifft.process(&mut column);
-            for row in 0..n {
-                data[row * n + col] = column[row];
-            }
-        }
-        // Scale the output.
-        let scale = 1.0 / (n * n) as f64;
-        for val in data.iter_mut() {
-            *val = *val * scale;
-        }
+    /// Computes the 2D FFT by applying the 1D FFT along rows then columns,
+    /// dispatched to whichever [`FftBackend`] this scrambler selected.
+    fn fft2d(&self, data: &mut [FloatComplex], n: usize) {
+        self.backend.forward(data, n);
+    }
+
+    /// Computes the 2D inverse FFT by applying the 1D IFFT along rows then
+    /// columns (scaled by `1/n²`), dispatched to the selected [`FftBackend`].
+    fn ifft2d(&self, data: &mut [FloatComplex], n: usize) {
+        self.backend.inverse(data, n);
     }
 
     /// Replaces the phase of each frequency coefficient while preserving its magnitude.
     /// For each coefficient, a random phase is generated and the new phase is computed as:
     ///    new_phase = orig_phase + intensity * (random_phase - orig_phase)
     /// The symmetric counterpart is set to the conjugate to maintain a real inverse FFT.
-    fn phase_scramble(&mut self, data: &mut [Complex64]) {
+    ///
+    /// When `options.frequency_range` is set, only coefficients whose
+    /// normalized radial frequency falls inside `[low, high]` are scrambled;
+    /// the rest are left untouched, enabling effects like "scramble only
+    /// high-frequency detail" while keeping coarse layout intact.
+    fn phase_scramble(&self, data: &mut [FloatComplex], rng: &mut StdRng) {
         let n = (data.len() as f64).sqrt() as usize;
         for y in 0..n {
             for x in 0..n {
@@ -195,14 +292,49 @@ impl FourierScrambler {
                 if y > sym_y || (y == sym_y && x > sym_x) {
                     continue;
                 }
+                if !self.in_frequency_band(x, y, n) {
+                    continue;
+                }
                 let idx = y * n + x;
                 let orig = data[idx];
                 let mag = orig.norm();
                 let orig_phase = orig.arg();
-                let random_phase = self.rng.gen_range(0.0..(2.0 * std::f64::consts::PI));
+                let random_phase = rng.gen_range(0.0..(2.0 * std::f64::consts::PI)) as Float;
                 let dphase = angle_difference(random_phase, orig_phase);
-                let new_phase = orig_phase + self.options.intensity as f64 * dphase;
-                let new_val = Complex64::from_polar(mag, new_phase);
+                let new_phase = orig_phase + self.options.intensity as Float * dphase;
+                let new_val = FloatComplex::from_polar(mag, new_phase);
+                data[idx] = new_val;
+                if !(y == sym_y && x == sym_x) {
+                    let sym_idx = sym_y * n + sym_x;
+                    data[sym_idx] = new_val.conj();
+                }
+            }
+        }
+    }
+
+    /// Walks the same symmetric coefficient order and frequency-band filter
+    /// as `phase_scramble`, regenerating the identical random-phase sequence
+    /// from `rng` and solving for each `orig_phase` via [`recover_orig_phase`].
+    fn phase_unscramble(&self, data: &mut [FloatComplex], rng: &mut StdRng) {
+        let n = (data.len() as f64).sqrt() as usize;
+        let intensity = self.options.intensity as Float;
+        for y in 0..n {
+            for x in 0..n {
+                let sym_y = if y == 0 { 0 } else { n - y };
+                let sym_x = if x == 0 { 0 } else { n - x };
+                if y > sym_y || (y == sym_y && x > sym_x) {
+                    continue;
+                }
+                if !self.in_frequency_band(x, y, n) {
+                    continue;
+                }
+                let idx = y * n + x;
+                let scrambled = data[idx];
+                let mag = scrambled.norm();
+                let scrambled_phase = scrambled.arg();
+                let random_phase = rng.gen_range(0.0..(2.0 * std::f64::consts::PI)) as Float;
+                let orig_phase = recover_orig_phase(scrambled_phase, random_phase, intensity);
+                let new_val = FloatComplex::from_polar(mag, orig_phase);
                 data[idx] = new_val;
                 if !(y == sym_y && x == sym_x) {
                     let sym_idx = sym_y * n + sym_x;
@@ -212,13 +344,26 @@ impl FourierScrambler {
         }
     }
 
-    /// Converts a 2D real array to a flat vector of Complex64.
-    fn to_complex(&self, real: &Array2<f64>) -> Vec<Complex64> {
-        real.iter().map(|&val| Complex64::new(val, 0.0)).collect()
+    /// Returns whether coefficient `(x, y)` of an `n x n` spectrum falls
+    /// inside `options.frequency_range`, or `true` if no range is set.
+    fn in_frequency_band(&self, x: usize, y: usize, n: usize) -> bool {
+        let Some(FrequencyRange { low, high }) = self.options.frequency_range else {
+            return true;
+        };
+        let half = n as Float / 2.0;
+        let fx = x.min(n - x) as Float / half;
+        let fy = y.min(n - y) as Float / half;
+        let r = (fx * fx + fy * fy).sqrt();
+        r >= low as Float && r <= high as Float
+    }
+
+    /// Converts a 2D real array to a flat vector of complex numbers.
+    fn to_complex(&self, real: &Array2<Float>) -> Vec<FloatComplex> {
+        real.iter().map(|&val| FloatComplex::new(val, 0.0)).collect()
     }
 
     /// Splits the input image into three channels (normalized to [0, 1]) as 2D arrays.
-    fn split_channels(&self, image: &DynamicImage) -> Result<Vec<Array2<f64>>> {
+    fn split_channels(&self, image: &DynamicImage) -> Result<Vec<Array2<Float>>> {
         let rgb = image.to_rgb8();
         let (width, height) = (self.width, self.height);
         let mut channels = Vec::with_capacity(3);
@@ -227,7 +372,7 @@ impl FourierScrambler {
             for y in 0..height {
                 for x in 0..width {
                     let pixel = rgb.get_pixel(x as u32, y as u32);
-                    channel[[y, x]] = pixel[c] as f64 / 255.0;
+                    channel[[y, x]] = pixel[c] as Float / 255.0;
                 }
             }
             channels.push(channel);
@@ -235,7 +380,7 @@ impl FourierScrambler {
         Ok(channels)
     }
     /// Combines three 2D arrays (for R, G, B channels) into a single image.
-    fn combine_channels(&self, channels: Vec<Array2<f64>>) -> Result<DynamicImage> {
+    fn combine_channels(&self, channels: Vec<Array2<Float>>) -> Result<DynamicImage> {
         let (width, height) = (self.width as u32, self.height as u32);
         let mut image = ImageBuffer::new(width, height);
         for y in 0..height {
@@ -249,9 +394,9 @@ impl FourierScrambler {
         Ok(DynamicImage::ImageRgb8(image))
     }
     /// Pads the given channel to a square of size (padded_size x padded_size) using the chosen mode.
-    fn apply_padding(&self, channel: &Array2<f64>) -> Result<Array2<f64>> {
+    fn apply_padding(&self, channel: &Array2<Float>) -> Result<Array2<Float>> {
         let (height, width) = channel.dim();
-        let padded_size = get_optimal_fft_size(width.max(height));
+        let padded_size = self.padded_size;
         let mut padded = Array2::zeros((padded_size, padded_size));
         match self.options.padding_mode {
             PaddingMode::Zero => {
@@ -292,9 +437,9 @@ impl FourierScrambler {
         Ok(padded)
     }
     /// Removes the padding from the inverse-transformed data.
-    fn remove_padding(&self, complex_data: &[Complex64], original_dim: (usize, usize)) -> Result<Array2<f64>> {
+    fn remove_padding(&self, complex_data: &[FloatComplex], original_dim: (usize, usize)) -> Result<Array2<Float>> {
         let (height, width) = original_dim;
-        let padded_size = get_optimal_fft_size(width.max(height));
+        let padded_size = self.padded_size;
         let mut result = Array2::zeros((height, width));
         for y in 0..height {
             for x in 0..width {
@@ -306,28 +451,258 @@ impl FourierScrambler {
     }
 }
 
-/// Returns the next power of two greater than or equal to `size`.
-fn get_optimal_fft_size(size: usize) -> usize {
-    let mut optimal_size = size;
-    while !is_power_of_two(optimal_size) {
-        optimal_size += 1;
+/// Swaps quadrants so the DC term (index `(0, 0)`) moves to the center of an
+/// `n x n` spectrum, the way `fftshift` does.
+fn fftshift(x: usize, y: usize, n: usize) -> (usize, usize) {
+    ((x + n / 2) % n, (y + n / 2) % n)
+}
+
+/// Renders `log(1 + |F|)`, normalized to the brightest coefficient, as a
+/// centered grayscale image.
+fn render_magnitude(data: &[FloatComplex], n: usize) -> ImageBuffer<Rgb<u8>, Vec<u8>> {
+    let log_mags: Vec<Float> = data.iter().map(|c| (1.0 + c.norm()).ln()).collect();
+    let max = log_mags.iter().cloned().fold(0.0, Float::max);
+    let mut image = ImageBuffer::new(n as u32, n as u32);
+    for y in 0..n {
+        for x in 0..n {
+            let normalized = if max > 0.0 { log_mags[y * n + x] / max } else { 0.0 };
+            let level = (normalized.clamp(0.0, 1.0) * 255.0) as u8;
+            let (sx, sy) = fftshift(x, y, n);
+            image.put_pixel(sx as u32, sy as u32, Rgb([level, level, level]));
+        }
     }
-    optimal_size
+    image
 }
 
-fn is_power_of_two(n: usize) -> bool {
-    n != 0 && (n & (n - 1)) == 0
+/// Renders each coefficient's phase as hue and its normalized magnitude as
+/// value (domain coloring), centered the same way as [`render_magnitude`].
+fn render_phase_domain_color(data: &[FloatComplex], n: usize) -> ImageBuffer<Rgb<u8>, Vec<u8>> {
+    let mags: Vec<Float> = data.iter().map(|c| c.norm()).collect();
+    let max = mags.iter().cloned().fold(0.0, Float::max);
+    let mut image = ImageBuffer::new(n as u32, n as u32);
+    for y in 0..n {
+        for x in 0..n {
+            let idx = y * n + x;
+            let hue = (data[idx].arg() + std::f64::consts::PI as Float)
+                / (2.0 * std::f64::consts::PI as Float)
+                * 360.0;
+            let value = if max > 0.0 { mags[idx] / max } else { 0.0 };
+            let (sx, sy) = fftshift(x, y, n);
+            image.put_pixel(sx as u32, sy as u32, Rgb(hsv_to_rgb(hue, 1.0, value.clamp(0.0, 1.0))));
+        }
+    }
+    image
+}
+
+/// Converts HSV (`h` in degrees `[0, 360)`, `s`/`v` in `[0, 1]`) to 8-bit RGB.
+fn hsv_to_rgb(h: Float, s: Float, v: Float) -> [u8; 3] {
+    let c = v * s;
+    let h_prime = h / 60.0;
+    let x = c * (1.0 - ((h_prime % 2.0) - 1.0).abs());
+    let (r1, g1, b1) = if h_prime < 1.0 {
+        (c, x, 0.0)
+    } else if h_prime < 2.0 {
+        (x, c, 0.0)
+    } else if h_prime < 3.0 {
+        (0.0, c, x)
+    } else if h_prime < 4.0 {
+        (0.0, x, c)
+    } else if h_prime < 5.0 {
+        (x, 0.0, c)
+    } else {
+        (c, 0.0, x)
+    };
+    let m = v - c;
+    [
+        ((r1 + m) * 255.0).round() as u8,
+        ((g1 + m) * 255.0).round() as u8,
+        ((b1 + m) * 255.0).round() as u8,
+    ]
+}
+
+/// Gathers an `n x n` row-major buffer into a column-major copy, i.e.
+/// `out[col * n + row] == data[row * n + col]`.
+pub(crate) fn transpose(data: &[FloatComplex], n: usize) -> Vec<FloatComplex> {
+    let mut out = vec![FloatComplex::new(0.0, 0.0); n * n];
+    for row in 0..n {
+        for col in 0..n {
+            out[col * n + row] = data[row * n + col];
+        }
+    }
+    out
+}
+
+/// Scatters a column-major buffer produced by [`transpose`] back into `data`.
+pub(crate) fn untranspose(columns: &[FloatComplex], data: &mut [FloatComplex], n: usize) {
+    for col in 0..n {
+        for row in 0..n {
+            data[row * n + col] = columns[col * n + row];
+        }
+    }
+}
+
+/// Returns the smallest 5-smooth integer (only prime factors 2, 3, 5)
+/// greater than or equal to `size`. rustfft handles these sizes with fast
+/// mixed-radix butterflies, so padding to the nearest 5-smooth size avoids
+/// the up-to-2x blowup (up to 4x in 2D) that next-power-of-two padding
+/// incurs while still keeping the transform efficient.
+pub(crate) fn get_optimal_fft_size(size: usize) -> usize {
+    let mut candidate = size.max(1);
+    while !is_five_smooth(candidate) {
+        candidate += 1;
+    }
+    candidate
+}
+
+fn is_five_smooth(mut n: usize) -> bool {
+    for factor in [2, 3, 5] {
+        while n % factor == 0 {
+            n /= factor;
+        }
+    }
+    n == 1
 }
 
 /// Computes the minimal angular difference between two angles (in radians),
 /// accounting for wrapping at ±π.
-fn angle_difference(a: f64, b: f64) -> f64 {
+fn angle_difference(a: Float, b: Float) -> Float {
     let mut diff = a - b;
-    while diff > std::f64::consts::PI {
-        diff -= 2.0 * std::f64::consts::PI;
+    while diff > std::f64::consts::PI as Float {
+        diff -= 2.0 * std::f64::consts::PI as Float;
     }
-    while diff < -std::f64::consts::PI {
-        diff += 2.0 * std::f64::consts::PI;
+    while diff < -std::f64::consts::PI as Float {
+        diff += 2.0 * std::f64::consts::PI as Float;
     }
     diff
 }
+
+/// Inverts `phase_scramble`'s wrap-prone phase formula by trying a handful of
+/// `2π`-shifted candidates and keeping the one whose re-scrambled phase is
+/// closest to `scrambled_phase`.
+fn recover_orig_phase(scrambled_phase: Float, random_phase: Float, intensity: Float) -> Float {
+    let two_pi = 2.0 * std::f64::consts::PI as Float;
+    (-1..=1)
+        .map(|k| {
+            let candidate = (scrambled_phase + two_pi * k as Float - intensity * random_phase)
+                / (1.0 - intensity);
+            let dphase = angle_difference(random_phase, candidate);
+            let predicted = angle_difference(candidate + intensity * dphase, 0.0);
+            (candidate, (predicted - scrambled_phase).abs())
+        })
+        .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap())
+        .map(|(candidate, _)| candidate)
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{ImageBuffer, Rgb};
+
+    #[test]
+    fn unscramble_reverses_scramble_with_zero_padding() {
+        let (width, height) = (6u32, 6u32);
+        let mut buf = ImageBuffer::new(width, height);
+        for y in 0..height {
+            for x in 0..width {
+                let v = ((x * 37 + y * 91) % 256) as u8;
+                buf.put_pixel(x, y, Rgb([v, v.wrapping_add(10), v.wrapping_add(20)]));
+            }
+        }
+        let image = DynamicImage::ImageRgb8(buf);
+        let options = FourierOptions {
+            phase_scramble: true,
+            intensity: 0.7,
+            padding_mode: PaddingMode::Zero,
+            frequency_range: None,
+        };
+
+        let mut scrambler = FourierScrambler::new(width as usize, height as usize, options.clone(), Some(42));
+        let scrambled = scrambler.scramble(&image).unwrap();
+        let mut unscrambler = FourierScrambler::new(width as usize, height as usize, options, Some(42));
+        let recovered = unscrambler.unscramble(&scrambled).unwrap();
+
+        let orig = image.to_rgb8();
+        let recon = recovered.to_rgb8();
+        for (orig_px, recon_px) in orig.pixels().zip(recon.pixels()) {
+            for c in 0..3 {
+                assert!(
+                    (orig_px[c] as i32 - recon_px[c] as i32).abs() <= 1,
+                    "pixel mismatch: {:?} vs {:?}",
+                    orig_px,
+                    recon_px
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn optimal_fft_size_is_five_smooth_and_minimal() {
+        assert_eq!(get_optimal_fft_size(1080), 1080); // 2^3 * 3^3 * 5
+        assert!(get_optimal_fft_size(1025) < 2048);
+        assert_eq!(get_optimal_fft_size(1), 1);
+        for size in [1, 2, 3, 5, 7, 100, 1025, 1080] {
+            let padded = get_optimal_fft_size(size);
+            assert!(padded >= size);
+            assert!(is_five_smooth(padded), "{padded} is not 5-smooth");
+        }
+    }
+
+    #[test]
+    fn five_smooth_rejects_other_prime_factors() {
+        assert!(is_five_smooth(1));
+        assert!(is_five_smooth(2 * 3 * 5));
+        assert!(!is_five_smooth(7));
+        assert!(!is_five_smooth(2 * 11));
+    }
+
+    #[test]
+    fn frequency_band_none_passes_everything() {
+        let options = FourierOptions {
+            phase_scramble: true,
+            intensity: 0.5,
+            padding_mode: PaddingMode::Zero,
+            frequency_range: None,
+        };
+        let scrambler = FourierScrambler::new(8, 8, options, Some(1));
+        assert!(scrambler.in_frequency_band(0, 4, 8));
+        assert!(scrambler.in_frequency_band(4, 4, 8));
+    }
+
+    #[test]
+    fn frequency_band_respects_low_and_high_edges() {
+        let options = FourierOptions {
+            phase_scramble: true,
+            intensity: 0.5,
+            padding_mode: PaddingMode::Zero,
+            frequency_range: Some(FrequencyRange { low: 0.4, high: 0.6 }),
+        };
+        let scrambler = FourierScrambler::new(8, 8, options, Some(1));
+        // DC (r = 0) is below the low edge.
+        assert!(!scrambler.in_frequency_band(0, 0, 8));
+        // Nyquist corner (r = sqrt(2) at n = 8) is above the high edge, even
+        // though each axis individually maxes out at r == 1.0.
+        assert!(!scrambler.in_frequency_band(4, 4, 8));
+        // x = 2, y = 0 -> r = 0.5, inside [0.4, 0.6].
+        assert!(scrambler.in_frequency_band(2, 0, 8));
+    }
+
+    #[test]
+    fn fftshift_moves_dc_to_center() {
+        assert_eq!(fftshift(0, 0, 8), (4, 4));
+        assert_eq!(fftshift(4, 4, 8), (0, 0));
+        assert_eq!(fftshift(7, 7, 8), (3, 3));
+    }
+
+    #[test]
+    fn hsv_to_rgb_primary_hues() {
+        assert_eq!(hsv_to_rgb(0.0, 1.0, 1.0), [255, 0, 0]);
+        assert_eq!(hsv_to_rgb(120.0, 1.0, 1.0), [0, 255, 0]);
+        assert_eq!(hsv_to_rgb(240.0, 1.0, 1.0), [0, 0, 255]);
+        // Hue wraps continuously: 359.9 deg sits just below 360 and should be
+        // close to 0 deg (red), not to the 300 deg magenta it has passed.
+        let near_wrap = hsv_to_rgb(359.9, 1.0, 1.0);
+        assert_eq!(near_wrap[0], 255);
+        assert_eq!(near_wrap[2], 0);
+    }
+}
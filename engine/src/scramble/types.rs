@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+
+/// How the channel is padded up to the FFT-friendly size before transforming.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PaddingMode {
+    Zero,
+    Reflect,
+    Wrap,
+}
+
+/// A band of normalized radial frequency, `0.0` at DC and `1.0` at Nyquist,
+/// used to restrict phase scrambling to a subset of a coefficient's spectrum.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FrequencyRange {
+    pub low: f32,
+    pub high: f32,
+}
+
+/// Which part of the frequency spectrum a diagnostic visualization renders.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SpectrumView {
+    /// Log-scaled magnitude, as grayscale.
+    Magnitude,
+    /// Phase mapped to hue and normalized magnitude mapped to value (domain coloring).
+    Phase,
+}
+
+/// Options controlling the Fourier (phase-scramble) transform.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FourierOptions {
+    pub phase_scramble: bool,
+    pub intensity: f32,
+    pub padding_mode: PaddingMode,
+    /// Restricts phase scrambling to coefficients whose radial frequency
+    /// falls within this band. `None` scrambles the whole spectrum.
+    pub frequency_range: Option<FrequencyRange>,
+}